@@ -0,0 +1,7 @@
+use crate::direction::Direction;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Quit,
+    Turn(Direction),
+}
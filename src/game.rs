@@ -1,15 +1,47 @@
 use crate::snake::Snake;
 use crate::point::Point;
 use crate::direction::Direction;
-use std::io::Stdout;
+use std::io::{Stdout, Write};
 use std::time::{Duration, Instant};
-use crossterm::terminal::size;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use crossterm::ExecutableCommand;
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{poll, read, Event, KeyCode, KeyEvent, KeyModifiers};
+use crossterm::style::Print;
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, size, Clear, ClearType, SetSize};
 use crate::command::Command;
+use crate::level::Level;
+use crate::wall_mode::WallMode;
 use rand::Rng;
 
 const MAX_INTERVAL: u16 = 700;
 const MIN_INTERVAL: u16 = 200;
 const MAX_SPEED: u16 = 20;
+const BONUS_SCORE_INTERVAL: u16 = 5;
+const BONUS_SCORE_VALUE: u16 = 5;
+const BONUS_GROWTH: u16 = 3;
+const BONUS_DURATION: Duration = Duration::from_secs(8);
+
+/// A point in the A* open set, ordered only by its score — `Point`
+/// itself has no meaningful ordering and isn't `Ord`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ScoredPoint {
+    score: u32,
+    point: Point,
+}
+
+impl Ord for ScoredPoint {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.score.cmp(&other.score)
+    }
+}
+
+impl PartialOrd for ScoredPoint {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
 
 #[derive(Debug)]
 pub struct Game {
@@ -18,13 +50,27 @@ pub struct Game {
     width: u16,
     height: u16,
     food: Option<Point>,
+    bonus_food: Option<(Point, Instant)>,
     snake: Snake,
     speed: u16,
     score: u16,
+    autopilot: bool,
+    turn_queue: VecDeque<Direction>,
+    max_queued_turns: usize,
+    wall_mode: WallMode,
+    obstacles: Vec<Point>,
 }
 
 impl Game {
-    pub fn new(stdout: Stdout, width: u16, height: u16) -> Self {
+    pub fn new(
+        stdout: Stdout,
+        width: u16,
+        height: u16,
+        autopilot: bool,
+        max_queued_turns: usize,
+        wall_mode: WallMode,
+        level: Level,
+    ) -> Self {
         let original_terminal_size: (u16, u16) = size().unwrap();
         Self {
             stdout,
@@ -32,10 +78,11 @@ impl Game {
             width,
             height,
             food: None,
+            bonus_food: None,
             snake: Snake::new(
                 Point::new(width / 2, height / 2),
                 3,
-                match rand::thread_rand().gen_range(0, 4) {
+                match rand::thread_rng().gen_range(0..4) {
                     0 => Direction::Up,
                     1 => Direction::Right,
                     2 => Direction::Down,
@@ -44,6 +91,11 @@ impl Game {
             ),
             speed: 0,
             score: 0,
+            autopilot,
+            turn_queue: VecDeque::new(),
+            max_queued_turns,
+            wall_mode,
+            obstacles: level.obstacles(width, height),
         }
     }
 
@@ -55,7 +107,6 @@ impl Game {
         let mut done = false;
         while !done {
             let interval = self.calculate_interval();
-            let direction = self.snake.get_direction();
             let now = Instant::now();
 
             while now.elapsed() < interval {
@@ -66,17 +117,35 @@ impl Game {
                             break;
                         }
                         Command::Turn(towards) => {
-                            if direction != towards && direction.opposite != towards {
-                                self.snake.set_direction(towards);
+                            if !self.autopilot {
+                                self.queue_turn(towards);
                             }
                         }
                     }
                 }
 
-                if self.has_collided_with_wall() || self.has_bitten_itself() {
+                if self.autopilot {
+                    self.steer_autopilot();
+                } else {
+                    self.apply_queued_turn();
+                }
+
+                if self.has_collided() || self.has_bitten_itself() {
                     done = true;
                 } else {
-                    self.snake.slither();
+                    self.snake.slither(self.wall_mode, self.width, self.height);
+
+                    if let Some((bonus_point, expires_at)) = self.bonus_food {
+                        if self.snake.get_head_point() == bonus_point {
+                            for _ in 0..BONUS_GROWTH {
+                                self.snake.grow();
+                            }
+                            self.score += BONUS_SCORE_VALUE;
+                            self.bonus_food = None;
+                        } else if Instant::now() >= expires_at {
+                            self.bonus_food = None;
+                        }
+                    }
 
                     if let Some(food_point) = self.food {
                         if self.snake.get_head_point() == food_point {
@@ -84,9 +153,13 @@ impl Game {
                             self.place_food();
                             self.score += 1;
 
-                            if self.score % ((self.width * self.height) / MAX_SPEED == 0) {
+                            if self.score.is_multiple_of((self.width * self.height) / MAX_SPEED) {
                                 self.speed += 1;
                             }
+
+                            if self.score.is_multiple_of(BONUS_SCORE_INTERVAL) {
+                                self.place_bonus_food();
+                            }
                         }
                     }
 
@@ -101,16 +174,58 @@ impl Game {
 
     fn place_food(&mut self) {
         loop {
-            let random_x = rand::thread_rng().gen_range(0, self.width);
-            let random_y = rand::thread_rng().gen_range(0, self.height);
+            let random_x = rand::thread_rng().gen_range(0..self.width);
+            let random_y = rand::thread_rng().gen_range(0..self.height);
             let point = Point::new(random_x, random_y);
-            if !self.snake.contains_point(&point) {
+            if !self.snake.contains_point(&point) && !self.obstacles.contains(&point) {
                 self.food = Some(point);
                 break;
             }
         }
     }
 
+    /// Spawns a transient bonus food cell that expires after
+    /// `BONUS_DURATION`, avoiding the snake's body and the normal food.
+    fn place_bonus_food(&mut self) {
+        loop {
+            let random_x = rand::thread_rng().gen_range(0..self.width);
+            let random_y = rand::thread_rng().gen_range(0..self.height);
+            let point = Point::new(random_x, random_y);
+            if !self.snake.contains_point(&point) && !self.obstacles.contains(&point) && self.food != Some(point) {
+                self.bonus_food = Some((point, Instant::now() + BONUS_DURATION));
+                break;
+            }
+        }
+    }
+
+    fn render(&mut self) {
+        self.stdout.execute(Clear(ClearType::All)).unwrap();
+
+        for obstacle_point in self.obstacles.clone() {
+            self.draw_cell(obstacle_point, '#');
+        }
+
+        if let Some(food_point) = self.food {
+            self.draw_cell(food_point, '*');
+        }
+
+        if let Some((bonus_point, _)) = self.bonus_food {
+            self.draw_cell(bonus_point, '$');
+        }
+
+        for body_point in self.snake.get_body_points().clone() {
+            self.draw_cell(body_point, 'o');
+        }
+
+        self.stdout.flush().unwrap();
+    }
+
+    fn draw_cell(&mut self, point: Point, symbol: char) {
+        self.stdout
+            .execute(MoveTo(point.x, point.y)).unwrap()
+            .execute(Print(symbol)).unwrap();
+    }
+
     fn prepare_ui(&mut self) {
         enable_raw_mode().unwrap();
         self.stdout
@@ -119,6 +234,15 @@ impl Game {
             .execute(Hide).unwrap();
     }
 
+    fn restore_ui(&mut self) {
+        let (original_width, original_height) = self.original_terminal_size;
+        disable_raw_mode().unwrap();
+        self.stdout
+            .execute(SetSize(original_width, original_height)).unwrap()
+            .execute(Clear(ClearType::All)).unwrap()
+            .execute(Show).unwrap();
+    }
+
     fn calculate_interval(&self) -> Duration {
         let speed = MAX_SPEED - self.speed;
         Duration::from_millis(
@@ -126,12 +250,41 @@ impl Game {
         )
     }
 
+    /// Pushes a turn onto the bounded FIFO queue, validated against the
+    /// last direction already sitting in the queue (or the snake's
+    /// current direction if the queue is empty). Drops the turn if it
+    /// would reverse into that direction or the queue is full.
+    fn queue_turn(&mut self, towards: Direction) {
+        let last_queued = self.turn_queue.back()
+            .copied()
+            .unwrap_or_else(|| self.snake.get_direction());
+
+        if towards != last_queued
+            && towards != last_queued.opposite()
+            && self.turn_queue.len() < self.max_queued_turns
+        {
+            self.turn_queue.push_back(towards);
+        }
+    }
+
+    /// Pops the next queued turn and applies it, validated against the
+    /// snake's actual last-applied direction rather than whatever was
+    /// true at the top of the tick.
+    fn apply_queued_turn(&mut self) {
+        if let Some(towards) = self.turn_queue.pop_front() {
+            let direction = self.snake.get_direction();
+            if towards != direction && towards != direction.opposite() {
+                self.snake.set_direction(towards);
+            }
+        }
+    }
+
     fn get_command(&self, wait_for: Duration) -> Option<Command> {
         let key_event = self.wait_for_key_event(wait_for)?;
 
         match key_event.code {
             KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => Some(Command::Quit),
-            KeyCode::Char('c') | KeyCode::('C') =>
+            KeyCode::Char('c') | KeyCode::Char('C') =>
                 if key_event.modifiers == KeyModifiers::CONTROL {
                     Some(Command::Quit)
                 } else {
@@ -140,6 +293,7 @@ impl Game {
             KeyCode::Up => Some(Command::Turn(Direction::Up)),
             KeyCode::Right => Some(Command::Turn(Direction::Right)),
             KeyCode::Down => Some(Command::Turn(Direction::Down)),
+            KeyCode::Left => Some(Command::Turn(Direction::Left)),
             _ => None
         }
     }
@@ -156,24 +310,205 @@ impl Game {
         None
     }
 
+    /// Transforms `point` by one step in `direction`, honoring the
+    /// current `wall_mode` (solid arenas saturate at the top and left
+    /// edges and step past the right and bottom ones, wrap arenas
+    /// reappear on the opposite side).
+    fn transform_point(&self, point: Point, direction: Direction) -> Point {
+        match self.wall_mode {
+            WallMode::Solid => point.transform(direction, 1),
+            WallMode::Wrap => point.wrapping_transform(direction, 1, self.width, self.height),
+        }
+    }
+
+    /// True if the snake has died: it hit a solid wall, or its next step
+    /// would land it on an obstacle.
+    fn has_collided(&self) -> bool {
+        if self.has_collided_with_wall() {
+            return true;
+        }
+
+        let next_head_point = self.transform_point(self.snake.get_head_point(), self.snake.get_direction());
+
+        self.obstacles.contains(&next_head_point)
+    }
+
     fn has_collided_with_wall(&self) -> bool {
+        if self.wall_mode == WallMode::Wrap {
+            return false;
+        }
+
         let head_point = self.snake.get_head_point();
 
         match self.snake.get_direction() {
-            Direction::Up => head_point.y <= 0,
+            Direction::Up => head_point.y == 0,
             Direction::Right => head_point.x >= self.width - 1,
             Direction::Down => head_point.y >= self.height - 1,
-            Direction::Left => head_point.x <= 0,
+            Direction::Left => head_point.x == 0,
         }
     }
 
     fn has_bitten_itself(&self) -> bool {
-        // TODO: where does the transform function come from? the Point crate?
-        let next_head_point = self.snake.get_head_point().transform(self.snake.get_direction(), 1);
+        let next_head_point = self.transform_point(self.snake.get_head_point(), self.snake.get_direction());
         let mut next_body_points = self.snake.get_body_points().clone();
         next_body_points.remove(next_body_points.len() - 1);
         next_body_points.remove(0);
 
         next_body_points.contains(&next_head_point)
     }
+
+    /// Steers the snake towards the food using A*, falling back to a
+    /// survival move when no path exists.
+    fn steer_autopilot(&mut self) {
+        let direction = self.find_path_to_food()
+            .unwrap_or_else(|| self.find_survival_direction());
+
+        self.snake.set_direction(direction);
+    }
+
+    /// Runs A* from the snake's head to the food, treating the body as
+    /// obstacles, and returns the `Direction` of the first step of the
+    /// path it finds. Returns `None` if the food is unreachable. The
+    /// Manhattan heuristic ignores the `Wrap` seam, so in that mode the
+    /// path found is not always the shortest one.
+    fn find_path_to_food(&self) -> Option<Direction> {
+        let food = self.food?;
+        let start = self.snake.get_head_point();
+        let obstacles = self.obstacle_points();
+        let current_direction = self.snake.get_direction();
+
+        let mut open_set = BinaryHeap::new();
+        let mut came_from: HashMap<Point, Point> = HashMap::new();
+        let mut g_score: HashMap<Point, u32> = HashMap::new();
+
+        g_score.insert(start, 0);
+        open_set.push(Reverse(ScoredPoint { score: Self::manhattan_distance(start, food), point: start }));
+
+        while let Some(Reverse(ScoredPoint { point: current, .. })) = open_set.pop() {
+            if current == food {
+                return self.reconstruct_first_step(&came_from, current, start, current_direction);
+            }
+
+            for neighbor in self.neighbors(current) {
+                if obstacles.contains(&neighbor) {
+                    continue;
+                }
+
+                // The very first step out of the head must not reverse
+                // into the snake's own neck.
+                if current == start && Self::direction_between(start, neighbor) == current_direction.opposite() {
+                    continue;
+                }
+
+                let tentative_g_score = g_score.get(&current).copied().unwrap_or(u32::MAX) + 1;
+                if tentative_g_score < g_score.get(&neighbor).copied().unwrap_or(u32::MAX) {
+                    came_from.insert(neighbor, current);
+                    g_score.insert(neighbor, tentative_g_score);
+                    let f_score = tentative_g_score + Self::manhattan_distance(neighbor, food);
+                    open_set.push(Reverse(ScoredPoint { score: f_score, point: neighbor }));
+                }
+            }
+        }
+
+        None
+    }
+
+    fn reconstruct_first_step(
+        &self,
+        came_from: &HashMap<Point, Point>,
+        mut current: Point,
+        start: Point,
+        fallback: Direction,
+    ) -> Option<Direction> {
+        while let Some(&point) = came_from.get(&current) {
+            let previous = current;
+            current = point;
+            if current == start {
+                return Some(Self::direction_between(start, previous));
+            }
+        }
+
+        Some(fallback)
+    }
+
+    /// Picks the neighboring free cell that maximizes reachable empty
+    /// space (via flood fill), so the snake stalls safely instead of
+    /// suiciding when no path to the food exists.
+    fn find_survival_direction(&self) -> Direction {
+        let head = self.snake.get_head_point();
+        let obstacles = self.obstacle_points();
+        let current_direction = self.snake.get_direction();
+
+        Direction::all()
+            .into_iter()
+            .filter(|&direction| direction.opposite() != current_direction)
+            .map(|direction| (direction, self.transform_point(head, direction)))
+            .filter(|(_, point)| (self.wall_mode == WallMode::Wrap || self.is_in_bounds(*point)) && !obstacles.contains(point))
+            .map(|(direction, point)| (direction, self.flood_fill_area(point, &obstacles)))
+            .max_by_key(|(_, area)| *area)
+            .map(|(direction, _)| direction)
+            .unwrap_or(current_direction)
+    }
+
+    fn flood_fill_area(&self, start: Point, obstacles: &HashSet<Point>) -> usize {
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
+        visited.insert(start);
+        queue.push_back(start);
+
+        while let Some(point) = queue.pop_front() {
+            for neighbor in self.neighbors(point) {
+                if !obstacles.contains(&neighbor) && visited.insert(neighbor) {
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        visited.len()
+    }
+
+    fn neighbors(&self, point: Point) -> Vec<Point> {
+        Direction::all()
+            .into_iter()
+            .map(|direction| self.transform_point(point, direction))
+            .filter(|neighbor| self.wall_mode == WallMode::Wrap || self.is_in_bounds(*neighbor))
+            .collect()
+    }
+
+    fn is_in_bounds(&self, point: Point) -> bool {
+        point.x < self.width && point.y < self.height
+    }
+
+    fn obstacle_points(&self) -> HashSet<Point> {
+        self.snake.get_body_points().iter()
+            .chain(self.obstacles.iter())
+            .copied()
+            .collect()
+    }
+
+    fn direction_between(from: Point, to: Point) -> Direction {
+        // Neighbors more than one cell apart sit on opposite sides of
+        // the `Wrap` seam, so the step runs the other way round.
+        if to.y > from.y + 1 {
+            Direction::Up
+        } else if to.y + 1 < from.y {
+            Direction::Down
+        } else if to.x > from.x + 1 {
+            Direction::Left
+        } else if to.x + 1 < from.x {
+            Direction::Right
+        } else if to.y < from.y {
+            Direction::Up
+        } else if to.x > from.x {
+            Direction::Right
+        } else if to.y > from.y {
+            Direction::Down
+        } else {
+            Direction::Left
+        }
+    }
+
+    fn manhattan_distance(a: Point, b: Point) -> u32 {
+        ((a.x as i32 - b.x as i32).abs() + (a.y as i32 - b.y as i32).abs()) as u32
+    }
 }
\ No newline at end of file
@@ -0,0 +1,63 @@
+use crate::point::Point;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Empty,
+    Cross,
+    BorderedRoom,
+}
+
+/// Manhattan radius, around the snake's spawn point, that built-in
+/// levels must leave free so the snake isn't born on top of a wall.
+const SPAWN_CLEARANCE: u16 = 2;
+
+impl Level {
+    pub fn obstacles(&self, width: u16, height: u16) -> Vec<Point> {
+        match self {
+            Level::Empty => Vec::new(),
+            Level::Cross => Self::cross_obstacles(width, height),
+            Level::BorderedRoom => Self::bordered_room_obstacles(width, height),
+        }
+    }
+
+    fn cross_obstacles(width: u16, height: u16) -> Vec<Point> {
+        let mid_x = width / 2;
+        let mid_y = height / 2;
+        let spawn = Point::new(mid_x, mid_y);
+        let mut obstacles = Vec::new();
+
+        for x in 0..width {
+            let point = Point::new(x, mid_y);
+            if Self::manhattan_distance(point, spawn) > SPAWN_CLEARANCE {
+                obstacles.push(point);
+            }
+        }
+        for y in 0..height {
+            let point = Point::new(mid_x, y);
+            if Self::manhattan_distance(point, spawn) > SPAWN_CLEARANCE {
+                obstacles.push(point);
+            }
+        }
+
+        obstacles
+    }
+
+    fn manhattan_distance(a: Point, b: Point) -> u16 {
+        a.x.abs_diff(b.x) + a.y.abs_diff(b.y)
+    }
+
+    fn bordered_room_obstacles(width: u16, height: u16) -> Vec<Point> {
+        let mut obstacles = Vec::new();
+
+        for x in 0..width {
+            obstacles.push(Point::new(x, 0));
+            obstacles.push(Point::new(x, height - 1));
+        }
+        for y in 0..height {
+            obstacles.push(Point::new(0, y));
+            obstacles.push(Point::new(width - 1, y));
+        }
+
+        obstacles
+    }
+}
@@ -0,0 +1,48 @@
+mod command;
+mod direction;
+mod game;
+mod level;
+mod point;
+mod snake;
+mod wall_mode;
+
+use crossterm::terminal::size;
+use game::Game;
+use level::Level;
+use wall_mode::WallMode;
+
+const MAX_QUEUED_TURNS: usize = 10;
+
+fn main() {
+    let (width, height) = size().unwrap();
+    let autopilot = has_flag("--autopilot");
+    let wall_mode = if has_flag("--wrap") { WallMode::Wrap } else { WallMode::Solid };
+    let level = level_arg();
+    let mut game = Game::new(
+        std::io::stdout(),
+        width - 3,
+        height - 3,
+        autopilot,
+        MAX_QUEUED_TURNS,
+        wall_mode,
+        level,
+    );
+    game.run();
+}
+
+/// True if `flag` (e.g. `--autopilot`) was passed on the command line.
+fn has_flag(flag: &str) -> bool {
+    std::env::args().any(|arg| arg == flag)
+}
+
+/// Reads `--level=<name>` off the command line (`cross`, `room`),
+/// defaulting to `Level::Empty` for anything else or no flag at all.
+fn level_arg() -> Level {
+    let name = std::env::args().find_map(|arg| arg.strip_prefix("--level=").map(str::to_owned));
+
+    match name.as_deref() {
+        Some("cross") => Level::Cross,
+        Some("room") => Level::BorderedRoom,
+        _ => Level::Empty,
+    }
+}
@@ -0,0 +1,38 @@
+use crate::direction::Direction;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: u16,
+    pub y: u16,
+}
+
+impl Point {
+    pub fn new(x: u16, y: u16) -> Self {
+        Self { x, y }
+    }
+
+    pub fn transform(&self, direction: Direction, amount: u16) -> Self {
+        match direction {
+            Direction::Up => Self::new(self.x, self.y.saturating_sub(amount)),
+            Direction::Right => Self::new(self.x + amount, self.y),
+            Direction::Down => Self::new(self.x, self.y + amount),
+            Direction::Left => Self::new(self.x.saturating_sub(amount), self.y),
+        }
+    }
+
+    /// Like `transform`, but coordinates that leave the `width x height`
+    /// arena reappear on the opposite edge instead of saturating.
+    pub fn wrapping_transform(&self, direction: Direction, amount: u16, width: u16, height: u16) -> Self {
+        let (dx, dy) = match direction {
+            Direction::Up => (0, -(amount as i32)),
+            Direction::Right => (amount as i32, 0),
+            Direction::Down => (0, amount as i32),
+            Direction::Left => (-(amount as i32), 0),
+        };
+
+        Self::new(
+            (self.x as i32 + dx).rem_euclid(width as i32) as u16,
+            (self.y as i32 + dy).rem_euclid(height as i32) as u16,
+        )
+    }
+}
@@ -0,0 +1,57 @@
+use crate::direction::Direction;
+use crate::point::Point;
+use crate::wall_mode::WallMode;
+
+#[derive(Debug)]
+pub struct Snake {
+    body: Vec<Point>,
+    direction: Direction,
+}
+
+impl Snake {
+    pub fn new(head: Point, length: u16, direction: Direction) -> Self {
+        let mut body = Vec::with_capacity(length as usize);
+        body.push(head);
+        for i in 1..length {
+            body.push(head.transform(direction.opposite(), i));
+        }
+
+        Self { body, direction }
+    }
+
+    pub fn get_head_point(&self) -> Point {
+        self.body[0]
+    }
+
+    pub fn get_body_points(&self) -> &Vec<Point> {
+        &self.body
+    }
+
+    pub fn get_direction(&self) -> Direction {
+        self.direction
+    }
+
+    pub fn set_direction(&mut self, direction: Direction) {
+        self.direction = direction;
+    }
+
+    pub fn slither(&mut self, wall_mode: WallMode, width: u16, height: u16) {
+        let head = self.get_head_point();
+        let next_head = match wall_mode {
+            WallMode::Solid => head.transform(self.direction, 1),
+            WallMode::Wrap => head.wrapping_transform(self.direction, 1, width, height),
+        };
+
+        self.body.insert(0, next_head);
+        self.body.pop();
+    }
+
+    pub fn grow(&mut self) {
+        let tail = *self.body.last().unwrap();
+        self.body.push(tail);
+    }
+
+    pub fn contains_point(&self, point: &Point) -> bool {
+        self.body.contains(point)
+    }
+}